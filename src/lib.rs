@@ -1,65 +1,740 @@
-//! A zero-dependency pure Rust prefix tree optimized for an English alphabet.
-//! Current implementation is not space efficient and could be further
-//! optimized. One approach is implementing a Patricia tree that groups common
-//! prefixes together, ultimately compressing the tree. Another way is to use a
-//! clever character encoding technique, which could also reduce the number of
-//! buckets. Speed-wise, the current implementation can load over 400, 000
-//! words in under 0.3 seconds and thus, is efficient enough for most
-//! applications. Searches for words are instantaneous. The downside, however,
-//! is that it took over 29, 000, 000 nodes for constructing this prefix tree.
+//! A zero-dependency pure Rust prefix tree generic over its key alphabet
+//! and, optionally, a value carried by each word.
+//!
+//! [`GenericPrefixTree<K, V>`] keys each node's children through the
+//! [`Alphabet`] trait, which lets the storage strategy vary with `K`
+//! instead of being fixed to one representation. [`PrefixTree`] is the
+//! default alias over [`AsciiLower`], whose children are a 26-slot array
+//! indexed directly by letter, so the common case of loading a large
+//! lowercase word list keeps the branch-free, hash-free speed the
+//! original array-based tree had (400k+ words in well under a second).
+//! Keys outside that alphabet, such as `char` for Unicode strings or
+//! `u8` for byte sequences, fall back to a `HashMap`-backed
+//! [`Alphabet`] implementation, trading the array's speed for coverage
+//! of an unbounded key space. The array-backed default narrows that
+//! trade-off but does not remove it: `PrefixTree`'s `&str` convenience
+//! methods (`insert`, `get`, `contains_word`, ...) still panic on any
+//! byte outside `a`-`z`, such as uppercase letters or non-ASCII
+//! characters. Use `GenericPrefixTree<char, V>` instead when words may
+//! contain such characters.
 
 #![warn(clippy::all, clippy::pedantic, missing_docs)]
 
-/// `Node` is a type that represents a node for a prefix tree.
-#[derive(Debug, Default, PartialEq)]
-pub struct Node {
-    /// Buckets.
-    pub buckets: [Option<Box<Node>>; 26],
-    /// Marker to specify end of word.
-    pub is_word: bool,
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::Hash;
+
+/// `Alphabet` is implemented by key types that [`GenericPrefixTree`] can
+/// be keyed on. It picks the concrete storage used for a node's
+/// children: [`AsciiLower`] uses a fixed-size array for its bounded,
+/// 26-letter alphabet, while other key types fall back to a
+/// `HashMap`-backed [`ChildMap`].
+pub trait Alphabet: Clone + Eq {
+    /// The storage type used for one node's children.
+    type Children<V>: ChildMap<Self, V>;
+}
+
+impl Alphabet for char {
+    type Children<V> = HashMap<char, Box<Node<char, V>>>;
+}
+
+impl Alphabet for u8 {
+    type Children<V> = HashMap<u8, Box<Node<u8, V>>>;
+}
+
+/// `ChildMap` is the storage interface a [`Node`]'s children must
+/// support. Implementations exist for `HashMap` (used by any key type
+/// falling back to hashing) and for [`AsciiLowerChildren`] (used by the
+/// array-backed default alphabet, [`AsciiLower`]).
+pub trait ChildMap<K: Alphabet, V>: Default {
+    /// Returns the child reached by `key`, if any.
+    fn get(&self, key: &K) -> Option<&Node<K, V>>;
+
+    /// Returns a mutable reference to the child reached by `key`, if any.
+    fn get_mut(&mut self, key: &K) -> Option<&mut Node<K, V>>;
+
+    /// Reports whether a child exists for `key`.
+    fn contains_key(&self, key: &K) -> bool;
+
+    /// Inserts `node` as the child reached by `key`.
+    fn insert(&mut self, key: K, node: Box<Node<K, V>>);
+
+    /// Removes the child reached by `key`, if any.
+    fn remove(&mut self, key: &K);
+
+    /// Reports whether this node has no children.
+    fn is_empty(&self) -> bool;
+
+    /// Iterates over every `(key, child)` pair, in no particular order.
+    fn iter(&self) -> Box<dyn Iterator<Item = (K, &Node<K, V>)> + '_>;
+}
+
+impl<K: Alphabet + Hash, V, S: std::hash::BuildHasher + Default> ChildMap<K, V>
+    for HashMap<K, Box<Node<K, V>>, S>
+{
+    fn get(&self, key: &K) -> Option<&Node<K, V>> {
+        HashMap::get(self, key).map(Box::as_ref)
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut Node<K, V>> {
+        HashMap::get_mut(self, key).map(Box::as_mut)
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        HashMap::contains_key(self, key)
+    }
+
+    fn insert(&mut self, key: K, node: Box<Node<K, V>>) {
+        HashMap::insert(self, key, node);
+    }
+
+    fn remove(&mut self, key: &K) {
+        HashMap::remove(self, key);
+    }
+
+    fn is_empty(&self) -> bool {
+        HashMap::is_empty(self)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (K, &Node<K, V>)> + '_> {
+        Box::new(HashMap::iter(self).map(|(key, node)| (key.clone(), node.as_ref())))
+    }
+}
+
+/// `AsciiLower` is a key representing a single lowercase ASCII letter
+/// (`'a'..='z'`). It is the key type behind [`PrefixTree`], the
+/// default, array-backed prefix tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AsciiLower(u8);
+
+impl AsciiLower {
+    /// Returns the 0-25 index of this letter within the alphabet.
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl TryFrom<char> for AsciiLower {
+    type Error = ();
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        if c.is_ascii_lowercase() {
+            Ok(AsciiLower(c as u8 - b'a'))
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl From<AsciiLower> for char {
+    fn from(key: AsciiLower) -> Self {
+        (b'a' + key.0) as char
+    }
+}
+
+impl Alphabet for AsciiLower {
+    type Children<V> = AsciiLowerChildren<V>;
+}
+
+/// `AsciiLowerChildren` is the array-backed child storage behind
+/// [`AsciiLower`]-keyed nodes: one slot per letter, indexed directly
+/// instead of hashed, which is what gives [`PrefixTree`] its speed.
+#[derive(Debug)]
+pub struct AsciiLowerChildren<V>([Option<Box<Node<AsciiLower, V>>>; 26]);
+
+impl<V> Default for AsciiLowerChildren<V> {
+    fn default() -> Self {
+        AsciiLowerChildren(std::array::from_fn(|_| None))
+    }
+}
+
+impl<V> ChildMap<AsciiLower, V> for AsciiLowerChildren<V> {
+    fn get(&self, key: &AsciiLower) -> Option<&Node<AsciiLower, V>> {
+        self.0[key.index()].as_deref()
+    }
+
+    fn get_mut(&mut self, key: &AsciiLower) -> Option<&mut Node<AsciiLower, V>> {
+        self.0[key.index()].as_deref_mut()
+    }
+
+    fn contains_key(&self, key: &AsciiLower) -> bool {
+        self.0[key.index()].is_some()
+    }
+
+    fn insert(&mut self, key: AsciiLower, node: Box<Node<AsciiLower, V>>) {
+        self.0[key.index()] = Some(node);
+    }
+
+    fn remove(&mut self, key: &AsciiLower) {
+        self.0[key.index()] = None;
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.iter().all(Option::is_none)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (AsciiLower, &Node<AsciiLower, V>)> + '_> {
+        Box::new(self.0.iter().enumerate().filter_map(|(idx, slot)| {
+            #[allow(clippy::cast_possible_truncation)]
+            slot.as_deref().map(|node| (AsciiLower(idx as u8), node))
+        }))
+    }
+}
+
+/// `CharAlphabet` is implemented by key types that can be driven
+/// directly from a `char`, which lets [`GenericPrefixTree`] offer
+/// `&str`-based convenience methods (`insert`, `get`, `words_with_prefix`,
+/// ...) without the caller manually building key sequences.
+pub trait CharAlphabet: Alphabet {
+    /// Converts a single `char` into this alphabet's key type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `c` falls outside the alphabet this key type represents.
+    fn from_char(c: char) -> Self;
+
+    /// Converts this key back into the `char` it represents.
+    fn to_char(self) -> char;
+}
+
+impl CharAlphabet for char {
+    fn from_char(c: char) -> Self {
+        c
+    }
+
+    fn to_char(self) -> char {
+        self
+    }
+}
+
+impl CharAlphabet for AsciiLower {
+    fn from_char(c: char) -> Self {
+        AsciiLower::try_from(c)
+            .unwrap_or_else(|()| panic!("not a lowercase ASCII letter: {c:?}"))
+    }
+
+    fn to_char(self) -> char {
+        self.into()
+    }
+}
+
+/// `Node` is a type that represents a node for a generic prefix tree,
+/// used internally by [`GenericPrefixTree`]. A node is a word terminal
+/// iff `value` is `Some`.
+pub struct Node<K: Alphabet, V = ()> {
+    /// Children, keyed by the next key in an inserted sequence.
+    pub children: K::Children<V>,
+    /// The value associated with the word ending at this node, if any.
+    pub value: Option<V>,
+}
+
+impl<K: Alphabet, V> fmt::Debug for Node<K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+    K::Children<V>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Node")
+            .field("children", &self.children)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<K: Alphabet, V> Default for Node<K, V> {
+    fn default() -> Self {
+        Node {
+            children: Default::default(),
+            value: None,
+        }
+    }
+}
+
+/// `GenericPrefixTree` is a type that represents a prefix tree keyed on
+/// an arbitrary `K`, optionally associating a value of type `V` with
+/// each stored word.
+pub struct GenericPrefixTree<K: Alphabet, V = ()> {
+    /// Root of the tree
+    pub root: Node<K, V>,
+    /// Number of nodes
+    pub num_nodes: u64,
+    /// Number of distinct words stored in the tree.
+    pub num_words: usize,
+}
+
+impl<K: Alphabet, V> fmt::Debug for GenericPrefixTree<K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+    K::Children<V>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GenericPrefixTree")
+            .field("root", &self.root)
+            .field("num_nodes", &self.num_nodes)
+            .field("num_words", &self.num_words)
+            .finish()
+    }
+}
+
+/// `PrefixTree` is the default prefix tree: words are keyed on
+/// [`AsciiLower`] with no associated value, so each node's children are
+/// a 26-slot array rather than a hash map. Use
+/// [`GenericPrefixTree<char>`](GenericPrefixTree) directly for Unicode
+/// words, or any other key type for non-textual sequences.
+pub type PrefixTree = GenericPrefixTree<AsciiLower>;
+
+impl<K: Alphabet, V> Default for GenericPrefixTree<K, V> {
+    fn default() -> Self {
+        GenericPrefixTree {
+            root: Node::default(),
+            num_nodes: 1,
+            num_words: 0,
+        }
+    }
+}
+
+impl<K: Alphabet, V> GenericPrefixTree<K, V> {
+    /// `new` creates a new prefix tree.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let pt = pt::PrefixTree::new();
+    /// dbg!(pt);
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `insert_keys_with` inserts a sequence of keys into a prefix tree,
+    /// associating `value` with the word.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - A sequence of keys to be inserted into a prefix tree.
+    /// * `value` - The value to associate with the inserted word.
+    ///
+    /// # Panics
+    ///
+    /// This function should never panic.
+    pub fn insert_keys_with<I: IntoIterator<Item = K>>(&mut self, keys: I, value: V) {
+        let mut ptr = &mut self.root;
+
+        for key in keys {
+            if !ptr.children.contains_key(&key) {
+                self.num_nodes += 1;
+                ptr.children.insert(key.clone(), Box::new(Node::default()));
+            }
+
+            // SAFETY: This is okay since we know that `ptr.children[&key]`
+            // is not `None`. In other words, calling `unwrap` on will not
+            // result in undefined behavior.
+            ptr = ptr.children.get_mut(&key).unwrap();
+        }
+
+        if ptr.value.is_none() {
+            self.num_words += 1;
+        }
+        ptr.value = Some(value);
+    }
+
+    /// `contains_keys` searches for a sequence of keys in a prefix tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - A sequence of keys to be searched in a prefix tree.
+    #[must_use]
+    pub fn contains_keys<I: IntoIterator<Item = K>>(&self, keys: I) -> bool {
+        self.get_keys(keys).is_some()
+    }
+
+    /// `contains_prefix_keys` searches for a key sequence prefix in a
+    /// prefix tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - A key sequence prefix to be searched in a prefix tree.
+    #[must_use]
+    pub fn contains_prefix_keys<I: IntoIterator<Item = K>>(&self, keys: I) -> bool {
+        let mut ptr = &self.root;
+
+        for key in keys {
+            match ptr.children.get(&key) {
+                Some(child) => ptr = child,
+                None => return false,
+            }
+        }
+
+        true
+    }
+
+    /// `get_keys` returns the value associated with a sequence of keys,
+    /// if the tree contains it.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - A sequence of keys to look up.
+    #[must_use]
+    pub fn get_keys<I: IntoIterator<Item = K>>(&self, keys: I) -> Option<&V> {
+        let mut ptr = &self.root;
+
+        for key in keys {
+            match ptr.children.get(&key) {
+                Some(child) => ptr = child,
+                None => return None,
+            }
+        }
+
+        ptr.value.as_ref()
+    }
+
+    /// `get_keys_mut` returns a mutable reference to the value associated
+    /// with a sequence of keys, if the tree contains it.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - A sequence of keys to look up.
+    #[must_use]
+    pub fn get_keys_mut<I: IntoIterator<Item = K>>(&mut self, keys: I) -> Option<&mut V> {
+        let mut ptr = &mut self.root;
+
+        for key in keys {
+            match ptr.children.get_mut(&key) {
+                Some(child) => ptr = child,
+                None => return None,
+            }
+        }
+
+        ptr.value.as_mut()
+    }
+
+    /// `remove_keys` removes the word reached by a sequence of keys,
+    /// returning `true` if it was present. The terminal node's value is
+    /// unset and then any now-empty, non-word nodes are pruned back up
+    /// the path, keeping `num_nodes` accurate.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - A sequence of keys identifying the word to remove.
+    pub fn remove_keys<I: IntoIterator<Item = K>>(&mut self, keys: I) -> bool {
+        let removed = Self::remove_rec(&mut self.root, keys.into_iter(), &mut self.num_nodes);
+
+        if removed {
+            self.num_words -= 1;
+        }
+
+        removed
+    }
+
+    /// `remove_rec` descends `node` along `keys`, unsets the terminal
+    /// node's value once the sequence is exhausted, and on the way back
+    /// up prunes any child that is left with no value and no children of
+    /// its own, decrementing `num_nodes` for each node dropped.
+    fn remove_rec<I: Iterator<Item = K>>(
+        node: &mut Node<K, V>,
+        mut keys: I,
+        num_nodes: &mut u64,
+    ) -> bool {
+        let Some(key) = keys.next() else {
+            return node.value.take().is_some();
+        };
+
+        let Some(child) = node.children.get_mut(&key) else {
+            return false;
+        };
+
+        let removed = Self::remove_rec(child, keys, num_nodes);
+
+        if removed && child.value.is_none() && child.children.is_empty() {
+            node.children.remove(&key);
+            *num_nodes -= 1;
+        }
+
+        removed
+    }
+
+    /// `len` returns the number of distinct words stored in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut pt = pt::PrefixTree::new();
+    ///
+    /// assert_eq!(pt.len(), 0);
+    ///
+    /// pt.insert("hello");
+    /// pt.insert("hell");
+    /// assert_eq!(pt.len(), 2);
+    ///
+    /// pt.insert("hello");
+    /// assert_eq!(pt.len(), 2);
+    /// ```
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.num_words
+    }
+
+    /// `sequences_with_prefix` returns every key sequence stored in the
+    /// tree that starts with `prefix`, stopping after `limit` results.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - A key sequence prefix for which to collect completions.
+    /// * `limit` - The maximum number of completions to return.
+    #[must_use]
+    pub fn sequences_with_prefix<I: IntoIterator<Item = K>>(
+        &self,
+        prefix: I,
+        limit: usize,
+    ) -> Vec<Vec<K>> {
+        self.sequences_with_prefix_values(prefix, limit)
+            .into_iter()
+            .map(|(keys, _)| keys)
+            .collect()
+    }
+
+    /// `sequences_with_prefix_values` is like
+    /// [`GenericPrefixTree::sequences_with_prefix`] but also yields a
+    /// reference to the value stored alongside each key sequence.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - A key sequence prefix for which to collect completions.
+    /// * `limit` - The maximum number of completions to return.
+    #[must_use]
+    pub fn sequences_with_prefix_values<I: IntoIterator<Item = K>>(
+        &self,
+        prefix: I,
+        limit: usize,
+    ) -> Vec<(Vec<K>, &V)> {
+        let mut ptr = &self.root;
+        let mut stack = Vec::new();
+
+        for key in prefix {
+            match ptr.children.get(&key) {
+                Some(child) => ptr = child,
+                None => return Vec::new(),
+            }
+            stack.push(key);
+        }
+
+        let mut sequences = Vec::new();
+
+        Self::collect_sequences(ptr, &mut stack, &mut sequences, limit);
+
+        sequences
+    }
+
+    /// `collect_sequences` performs a depth-first traversal from `node`,
+    /// reconstructing each key sequence on `stack` and pushing it, paired
+    /// with its value, onto `sequences` once a word node is reached,
+    /// stopping once `limit` results have been collected.
+    fn collect_sequences<'a>(
+        node: &'a Node<K, V>,
+        stack: &mut Vec<K>,
+        sequences: &mut Vec<(Vec<K>, &'a V)>,
+        limit: usize,
+    ) {
+        if sequences.len() >= limit {
+            return;
+        }
+
+        if let Some(value) = &node.value {
+            sequences.push((stack.clone(), value));
+        }
+
+        for (key, child) in node.children.iter() {
+            if sequences.len() >= limit {
+                return;
+            }
+
+            stack.push(key);
+            Self::collect_sequences(child, stack, sequences, limit);
+            stack.pop();
+        }
+    }
+
+    /// `nodes_total` returns a total number of `Node`s in a
+    /// `GenericPrefixTree`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut pt = pt::PrefixTree::new();
+    ///
+    /// pt.insert("hello");
+    /// assert_eq!(pt.nodes_total(), 6);
+    ///
+    /// pt.insert("hell");
+    /// assert_eq!(pt.nodes_total(), 6);
+    ///
+    /// pt.insert("hellicopter");
+    /// assert_eq!(pt.nodes_total(), 13);
+    /// ```
+    #[must_use]
+    pub fn nodes_total(&self) -> u64 {
+        self.num_nodes
+    }
+
+    /// `is_empty` checks whether a prefix tree is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut pt = pt::PrefixTree::new();
+    /// let word = "bye";
+    ///
+    /// assert_eq!(pt.is_empty(), true);
+    ///
+    /// pt.insert(word);
+    /// assert_eq!(pt.is_empty(), false);
+    /// assert_eq!(pt.contains_word(word), true);
+    ///
+    /// pt.clear();
+    /// assert_eq!(pt.contains_word(word), false);
+    /// assert_eq!(pt.is_empty(), true);
+    /// ```
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.root.children.is_empty() && self.root.value.is_none()
+    }
+
+    /// `clear` clears a prefix tree.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut pt = pt::PrefixTree::new();
+    /// let word = "hi";
+    ///
+    /// pt.insert(word);
+    /// assert_eq!(pt.contains_word(word), true);
+    ///
+    /// pt.clear();
+    /// assert_eq!(pt.contains_word(word), false);
+    /// ```
+    pub fn clear(&mut self) {
+        self.root = Node::default();
+        self.num_nodes = 1;
+        self.num_words = 0;
+    }
+}
+
+impl<K: Alphabet> GenericPrefixTree<K, ()> {
+    /// `insert_keys` inserts a sequence of keys into a prefix tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - A sequence of keys to be inserted into a prefix tree.
+    pub fn insert_keys<I: IntoIterator<Item = K>>(&mut self, keys: I) {
+        self.insert_keys_with(keys, ());
+    }
 }
 
-/// `PrefixTree` is a type that represents a prefix tree.
-#[derive(Debug)]
-pub struct PrefixTree {
-    /// Root of the tree
-    pub root: Node,
-    /// Number of nodes
-    pub num_nodes: u64,
-}
+impl<K: CharAlphabet, V> GenericPrefixTree<K, V> {
+    /// `insert_with` inserts a word into a prefix tree, associating
+    /// `value` with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - A word to be inserted into a prefix tree.
+    /// * `value` - The value to associate with `word`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut pt: pt::GenericPrefixTree<char, u32> = pt::GenericPrefixTree::new();
+    ///
+    /// pt.insert_with("hello", 42);
+    /// assert_eq!(pt.get("hello"), Some(&42));
+    /// ```
+    pub fn insert_with(&mut self, word: &str, value: V) {
+        self.insert_keys_with(word.chars().map(K::from_char), value);
+    }
+
+    /// `get` returns the value associated with `word`, if the tree
+    /// contains it.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - A word to look up.
+    #[must_use]
+    pub fn get(&self, word: &str) -> Option<&V> {
+        self.get_keys(word.chars().map(K::from_char))
+    }
 
-impl Default for PrefixTree {
-    fn default() -> Self {
-        PrefixTree {
-            root: Node::default(),
-            num_nodes: 26,
-        }
+    /// `get_mut` returns a mutable reference to the value associated
+    /// with `word`, if the tree contains it.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - A word to look up.
+    #[must_use]
+    pub fn get_mut(&mut self, word: &str) -> Option<&mut V> {
+        self.get_keys_mut(word.chars().map(K::from_char))
     }
-}
 
-impl PrefixTree {
-    /// `new` creates a new prefix tree.
+    /// `remove` removes `word` from the tree, returning `true` if it was
+    /// present.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - A word to remove from the tree.
     ///
     /// # Example
     ///
     /// ```
-    /// let pt = pt::PrefixTree::new();
-    /// dbg!(pt);
+    /// let mut pt = pt::PrefixTree::new();
+    ///
+    /// pt.insert("hello");
+    /// pt.insert("help");
+    ///
+    /// assert_eq!(pt.remove("hello"), true);
+    /// assert_eq!(pt.contains_word("hello"), false);
+    /// assert_eq!(pt.contains_prefix("hel"), true);
+    /// assert_eq!(pt.remove("hello"), false);
     /// ```
-    #[must_use]
-    pub fn new() -> Self {
-        Self::default()
+    pub fn remove(&mut self, word: &str) -> bool {
+        self.remove_keys(word.chars().map(K::from_char))
     }
 
-    /// `index` returns an appropriate index based on character.
+    /// `words_with_prefix_values` is like
+    /// [`GenericPrefixTree::words_with_prefix`] but also yields a
+    /// reference to the value stored alongside each word.
     ///
     /// # Arguments
     ///
-    /// * `char` - A character for which to calculate an index.
-    fn index(c: char) -> usize {
-        (c as u8 - 97) as usize
+    /// * `prefix` - A prefix for which to collect completions.
+    /// * `limit` - The maximum number of completions to return.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut pt: pt::GenericPrefixTree<char, u32> = pt::GenericPrefixTree::new();
+    ///
+    /// pt.insert_with("hello", 1);
+    /// pt.insert_with("help", 2);
+    ///
+    /// let mut words = pt.words_with_prefix_values("hel", usize::MAX);
+    /// words.sort();
+    /// assert_eq!(
+    ///     words,
+    ///     vec![("hello".to_string(), &1), ("help".to_string(), &2)]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn words_with_prefix_values(&self, prefix: &str, limit: usize) -> Vec<(String, &V)> {
+        self.sequences_with_prefix_values(prefix.chars().map(K::from_char), limit)
+            .into_iter()
+            .map(|(keys, value)| (keys.into_iter().map(K::to_char).collect(), value))
+            .collect()
     }
+}
 
+impl<K: CharAlphabet> GenericPrefixTree<K, ()> {
     /// `insert` inserts a word into a prefix tree.
     ///
     /// # Arguments
@@ -80,21 +755,7 @@ impl PrefixTree {
     /// assert_eq!(pt.contains_word(word), true);
     /// ```
     pub fn insert(&mut self, word: &str) {
-        let mut ptr = &mut self.root;
-
-        for idx in word.chars().map(Self::index) {
-            if ptr.buckets[idx].is_none() {
-                self.num_nodes += 26;
-                ptr.buckets[idx] = Some(Box::new(Node::default()));
-            }
-
-            // SAFETY: This is okay since we know that `ptr.buckets[idx]` is
-            // not `None`. In other words, calling `unwrap` on will not result
-            // in undefined behavior.
-            ptr = ptr.buckets[idx].as_deref_mut().unwrap();
-        }
-
-        ptr.is_word = true;
+        self.insert_with(word, ());
     }
 
     /// `contains_word` searches for a word in a prefix tree.
@@ -114,16 +775,7 @@ impl PrefixTree {
     /// ```
     #[must_use]
     pub fn contains_word(&self, word: &str) -> bool {
-        let mut ptr = &self.root;
-
-        for idx in word.chars().map(Self::index) {
-            match &ptr.buckets[idx] {
-                Some(bucket) => ptr = bucket,
-                None => return false,
-            }
-        }
-
-        ptr.is_word
+        self.contains_keys(word.chars().map(K::from_char))
     }
 
     /// `contains_prefix` searches for a prefix word in a prefix tree.
@@ -147,19 +799,79 @@ impl PrefixTree {
     /// ```
     #[must_use]
     pub fn contains_prefix(&self, word: &str) -> bool {
-        let mut ptr = &self.root;
+        self.contains_prefix_keys(word.chars().map(K::from_char))
+    }
 
-        for idx in word.chars().map(Self::index) {
-            match &ptr.buckets[idx] {
-                Some(bucket) => ptr = bucket,
-                None => return false,
-            }
-        }
+    /// `words_with_prefix` returns every word stored in the tree that
+    /// starts with `prefix`.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - A prefix for which to collect completions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut pt = pt::PrefixTree::new();
+    ///
+    /// pt.insert("hello");
+    /// pt.insert("help");
+    /// pt.insert("world");
+    ///
+    /// let mut words = pt.words_with_prefix("hel");
+    /// words.sort();
+    /// assert_eq!(words, vec!["hello".to_string(), "help".to_string()]);
+    /// ```
+    #[must_use]
+    pub fn words_with_prefix(&self, prefix: &str) -> Vec<String> {
+        self.words_with_prefix_limit(prefix, usize::MAX)
+    }
 
-        true
+    /// `words_with_prefix_limit` is like [`GenericPrefixTree::words_with_prefix`]
+    /// but stops collecting after `limit` results, which makes it usable
+    /// for interactive completion where only a handful of suggestions are
+    /// shown.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - A prefix for which to collect completions.
+    /// * `limit` - The maximum number of completions to return.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut pt = pt::PrefixTree::new();
+    ///
+    /// pt.insert("hello");
+    /// pt.insert("help");
+    ///
+    /// assert_eq!(pt.words_with_prefix_limit("hel", 1).len(), 1);
+    /// ```
+    #[must_use]
+    pub fn words_with_prefix_limit(&self, prefix: &str, limit: usize) -> Vec<String> {
+        self.sequences_with_prefix(prefix.chars().map(K::from_char), limit)
+            .into_iter()
+            .map(|keys| keys.into_iter().map(K::to_char).collect())
+            .collect()
     }
+}
 
-    /// `nodes_total` returns a total number of `Node`s in a `PrefixTree`.
+impl GenericPrefixTree<AsciiLower, ()> {
+    /// `search_within_distance` returns every stored word whose
+    /// Levenshtein distance to `query` is at most `max_distance`, paired
+    /// with that distance.
+    ///
+    /// Rather than recomputing a full edit-distance matrix per candidate,
+    /// a single dynamic-programming row is threaded through the trie
+    /// traversal: descending into a child for character `c` derives the
+    /// next row from the parent's row in constant extra work per column,
+    /// and a subtree is pruned entirely once the minimum of its row
+    /// exceeds `max_distance`, since distances only grow deeper.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The word to compare stored entries against.
+    /// * `max_distance` - The maximum Levenshtein distance to accept.
     ///
     /// # Example
     ///
@@ -167,58 +879,301 @@ impl PrefixTree {
     /// let mut pt = pt::PrefixTree::new();
     ///
     /// pt.insert("hello");
-    /// assert_eq!(pt.nodes_total(), 156);
+    /// pt.insert("help");
     ///
-    /// pt.insert("hell");
-    /// assert_eq!(pt.nodes_total(), 156);
+    /// let mut matches = pt.search_within_distance("hallo", 1);
+    /// matches.sort();
+    /// assert_eq!(matches, vec![("hello".to_string(), 1)]);
+    /// ```
+    #[must_use]
+    pub fn search_within_distance(&self, query: &str, max_distance: usize) -> Vec<(String, usize)> {
+        let query_chars: Vec<char> = query.chars().collect();
+        let row: Vec<usize> = (0..=query_chars.len()).collect();
+
+        let mut matches = Vec::new();
+        let mut stack = Vec::new();
+
+        Self::search_within_distance_rec(
+            &self.root,
+            &query_chars,
+            &row,
+            &mut stack,
+            max_distance,
+            &mut matches,
+        );
+
+        matches
+    }
+
+    /// `search_within_distance_rec` extends `prev_row` one character
+    /// below `node`, reconstructing the candidate word on `stack` and
+    /// recording it in `matches` when it is within `max_distance` of the
+    /// query, pruning subtrees whose row can no longer satisfy the bound.
+    fn search_within_distance_rec(
+        node: &Node<AsciiLower>,
+        query_chars: &[char],
+        prev_row: &[usize],
+        stack: &mut Vec<char>,
+        max_distance: usize,
+        matches: &mut Vec<(String, usize)>,
+    ) {
+        for (key, child) in node.children.iter() {
+            let c = key.to_char();
+
+            let mut row = vec![prev_row[0] + 1];
+            for (j, &query_char) in query_chars.iter().enumerate() {
+                let substitution_cost = usize::from(query_char != c);
+                row.push(
+                    (row[j] + 1)
+                        .min(prev_row[j + 1] + 1)
+                        .min(prev_row[j] + substitution_cost),
+                );
+            }
+
+            if *row.iter().min().unwrap() > max_distance {
+                continue;
+            }
+
+            stack.push(c);
+
+            if child.value.is_some() {
+                let distance = row[query_chars.len()];
+                if distance <= max_distance {
+                    matches.push((stack.iter().collect(), distance));
+                }
+            }
+
+            Self::search_within_distance_rec(
+                child,
+                query_chars,
+                &row,
+                stack,
+                max_distance,
+                matches,
+            );
+
+            stack.pop();
+        }
+    }
+
+    /// `completion_mask` navigates to the node for `prefix` and returns a
+    /// 26-bit mask where bit `i` is set iff the letter `('a' as u8 + i)`
+    /// can legally follow `prefix`.
     ///
-    /// pt.insert("hellicopter");
-    /// assert_eq!(pt.nodes_total(), 338);
+    /// # Arguments
+    ///
+    /// * `prefix` - A prefix for which to compute the next-letter mask.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut pt = pt::PrefixTree::new();
+    ///
+    /// pt.insert("hello");
+    /// pt.insert("help");
+    ///
+    /// // Both 'l' (index 11) and 'p' (index 15) can follow "hel".
+    /// assert_eq!(pt.completion_mask("hel"), (1 << 11) | (1 << 15));
+    /// assert_eq!(pt.completion_mask("xyz"), 0);
     /// ```
     #[must_use]
-    pub fn nodes_total(&self) -> u64 {
-        self.num_nodes
+    pub fn completion_mask(&self, prefix: &str) -> u32 {
+        let mut ptr = &self.root;
+
+        for c in prefix.chars() {
+            match ptr.children.get(&AsciiLower::from_char(c)) {
+                Some(child) => ptr = child,
+                None => return 0,
+            }
+        }
+
+        let mut mask = 0u32;
+        for i in 0..26u8 {
+            if ptr.children.contains_key(&AsciiLower(i)) {
+                mask |= 1 << i;
+            }
+        }
+
+        mask
     }
 
-    /// `is_empty` checks whether a prefix tree is empty.
+    /// `complete_word` returns the unique completion of `prefix` when the
+    /// path from the prefix node is non-branching down to a single word,
+    /// which is useful for keyboards and guided text entry that want to
+    /// know the only possible next characters.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - A prefix to complete.
     ///
     /// # Example
     ///
     /// ```
     /// let mut pt = pt::PrefixTree::new();
-    /// let word = "bye";
     ///
-    /// assert_eq!(pt.is_empty(), true);
+    /// pt.insert("hello");
     ///
-    /// pt.insert(word);
-    /// assert_eq!(pt.is_empty(), false);
-    /// assert_eq!(pt.contains_word(word), true);
+    /// assert_eq!(pt.complete_word("hel"), Some("hello".to_string()));
     ///
-    /// pt.clear();
-    /// assert_eq!(pt.contains_word(word), false);
-    /// assert_eq!(pt.is_empty(), true);
+    /// pt.insert("help");
+    /// assert_eq!(pt.complete_word("hel"), None);
     /// ```
     #[must_use]
-    pub fn is_empty(&self) -> bool {
-        self.root == Node::default()
+    pub fn complete_word(&self, prefix: &str) -> Option<String> {
+        let mut ptr = &self.root;
+
+        for c in prefix.chars() {
+            match ptr.children.get(&AsciiLower::from_char(c)) {
+                Some(child) => ptr = child,
+                None => return None,
+            }
+        }
+
+        let mut word = prefix.to_string();
+
+        loop {
+            let mut children = ptr.children.iter();
+
+            match (children.next(), children.next()) {
+                (None, _) => return ptr.value.is_some().then_some(word),
+                (Some((key, child)), None) if ptr.value.is_none() => {
+                    word.push(key.to_char());
+                    ptr = child;
+                }
+                _ => return None,
+            }
+        }
     }
 
-    /// `clear` clears a prefix tree.
+    /// `ranked_suggestions` collects prefix and near-miss (within edit
+    /// distance 2) candidates for `query` and orders them by
+    /// Jaro-Winkler similarity to `query`, highest first, returning at
+    /// most `limit` results. This gives a "did you mean" ordering that
+    /// plain edit distance cannot, since it rewards a shared prefix and
+    /// penalizes transposed characters.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The word to rank suggestions against.
+    /// * `limit` - The maximum number of suggestions to return.
     ///
     /// # Example
     ///
     /// ```
     /// let mut pt = pt::PrefixTree::new();
-    /// let word = "hi";
     ///
-    /// pt.insert(word);
-    /// assert_eq!(pt.contains_word(word), true);
+    /// pt.insert("hello");
+    /// pt.insert("help");
+    /// pt.insert("world");
     ///
-    /// pt.clear();
-    /// assert_eq!(pt.contains_word(word), false);
+    /// let suggestions = pt.ranked_suggestions("helo", 2);
+    /// assert_eq!(suggestions[0].0, "hello");
     /// ```
-    pub fn clear(&mut self) {
-        self.root = Node::default();
+    #[must_use]
+    pub fn ranked_suggestions(&self, query: &str, limit: usize) -> Vec<(String, f64)> {
+        let mut seen = HashSet::new();
+        let mut candidates = Vec::new();
+
+        for word in self
+            .words_with_prefix(query)
+            .into_iter()
+            .chain(self.search_within_distance(query, 2).into_iter().map(|(word, _)| word))
+        {
+            if seen.insert(word.clone()) {
+                candidates.push(word);
+            }
+        }
+
+        let mut scored: Vec<(String, f64)> = candidates
+            .into_iter()
+            .map(|word| {
+                let score = Self::jaro_winkler_similarity(query, &word);
+                (word, score)
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(limit);
+
+        scored
+    }
+
+    /// `jaro_similarity` computes the Jaro similarity between `s1` and
+    /// `s2`: the fraction of characters that match within a bounded
+    /// window, adjusted for transpositions among the matched characters.
+    // Word lengths stay far below 2^52, so the `usize`-to-`f64` casts
+    // below cannot lose precision in practice.
+    #[allow(clippy::cast_precision_loss)]
+    fn jaro_similarity(s1: &str, s2: &str) -> f64 {
+        let s1: Vec<char> = s1.chars().collect();
+        let s2: Vec<char> = s2.chars().collect();
+
+        if s1.is_empty() && s2.is_empty() {
+            return 1.0;
+        }
+        if s1.is_empty() || s2.is_empty() {
+            return 0.0;
+        }
+
+        let match_distance = (s1.len().max(s2.len()) / 2).saturating_sub(1);
+
+        let mut s1_matches = vec![false; s1.len()];
+        let mut s2_matches = vec![false; s2.len()];
+        let mut matches = 0usize;
+
+        for (i, &c1) in s1.iter().enumerate() {
+            let start = i.saturating_sub(match_distance);
+            let end = (i + match_distance + 1).min(s2.len());
+
+            for (j, matched) in s2_matches.iter_mut().enumerate().take(end).skip(start) {
+                if *matched || s2[j] != c1 {
+                    continue;
+                }
+                s1_matches[i] = true;
+                *matched = true;
+                matches += 1;
+                break;
+            }
+        }
+
+        if matches == 0 {
+            return 0.0;
+        }
+
+        let mut transpositions = 0usize;
+        let mut k = 0;
+        for (i, &is_matched) in s1_matches.iter().enumerate() {
+            if !is_matched {
+                continue;
+            }
+            while !s2_matches[k] {
+                k += 1;
+            }
+            if s1[i] != s2[k] {
+                transpositions += 1;
+            }
+            k += 1;
+        }
+
+        let m = matches as f64;
+        (m / s1.len() as f64 + m / s2.len() as f64 + (m - (transpositions / 2) as f64) / m) / 3.0
+    }
+
+    /// `jaro_winkler_similarity` applies the Winkler boost to the Jaro
+    /// similarity of `s1` and `s2`, rewarding a common prefix (capped at
+    /// 4 characters) with weight `p = 0.1`.
+    #[allow(clippy::cast_precision_loss)]
+    fn jaro_winkler_similarity(s1: &str, s2: &str) -> f64 {
+        let jaro = Self::jaro_similarity(s1, s2);
+
+        let prefix_len = s1
+            .chars()
+            .zip(s2.chars())
+            .take(4)
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        jaro + prefix_len as f64 * 0.1 * (1.0 - jaro)
     }
 }
 
@@ -267,7 +1222,7 @@ mod tests {
             assert_eq!(pt.contains_word(word), true);
             assert_eq!(pt.contains_prefix(word), true);
         }
-        assert_eq!(pt.nodes_total(), 858);
+        assert_eq!(pt.nodes_total(), 33);
     }
 
     #[test]
@@ -287,9 +1242,164 @@ mod tests {
                 assert_eq!(pt.contains_prefix(&word[idx..]), false);
             }
         }
-        assert_eq!(pt.nodes_total(), 728);
+        assert_eq!(pt.nodes_total(), 28);
 
         pt.clear();
         assert_eq!(pt.is_empty(), true);
     }
+
+    #[test]
+    fn words_with_prefix() {
+        let mut pt = PrefixTree::new();
+
+        pt.insert("hello");
+        pt.insert("help");
+        pt.insert("helicopter");
+        pt.insert("world");
+
+        let mut words = pt.words_with_prefix("hel");
+        words.sort();
+        assert_eq!(
+            words,
+            vec![
+                "helicopter".to_string(),
+                "hello".to_string(),
+                "help".to_string(),
+            ]
+        );
+
+        assert_eq!(pt.words_with_prefix("xyz"), Vec::<String>::new());
+        assert_eq!(pt.words_with_prefix_limit("hel", 2).len(), 2);
+    }
+
+    #[test]
+    fn search_within_distance() {
+        let mut pt = PrefixTree::new();
+
+        pt.insert("hello");
+        pt.insert("help");
+        pt.insert("world");
+
+        let mut matches = pt.search_within_distance("hallo", 1);
+        matches.sort();
+        assert_eq!(matches, vec![("hello".to_string(), 1)]);
+
+        let mut matches = pt.search_within_distance("hel", 2);
+        matches.sort();
+        assert_eq!(
+            matches,
+            vec![("hello".to_string(), 2), ("help".to_string(), 1)]
+        );
+
+        assert_eq!(pt.search_within_distance("xyz", 1), Vec::new());
+    }
+
+    #[test]
+    fn completion_mask_and_complete_word() {
+        let mut pt = PrefixTree::new();
+
+        pt.insert("hello");
+
+        assert_eq!(pt.completion_mask("hel"), 1 << 11);
+        assert_eq!(pt.completion_mask("xyz"), 0);
+        assert_eq!(pt.complete_word("hel"), Some("hello".to_string()));
+        assert_eq!(pt.complete_word("hello"), Some("hello".to_string()));
+        assert_eq!(pt.complete_word("xyz"), None);
+
+        pt.insert("help");
+
+        assert_eq!(pt.completion_mask("hel"), (1 << 11) | (1 << 15));
+        assert_eq!(pt.complete_word("hel"), None);
+    }
+
+    #[test]
+    fn generic_keys() {
+        let mut pt: GenericPrefixTree<u8> = GenericPrefixTree::new();
+
+        pt.insert_keys([1, 2, 3]);
+        pt.insert_keys([1, 2, 4]);
+
+        assert_eq!(pt.contains_keys([1, 2, 3]), true);
+        assert_eq!(pt.contains_keys([1, 2]), false);
+        assert_eq!(pt.contains_prefix_keys([1, 2]), true);
+        assert_eq!(pt.contains_prefix_keys([9]), false);
+
+        let mut sequences = pt.sequences_with_prefix([1, 2], usize::MAX);
+        sequences.sort();
+        assert_eq!(sequences, vec![vec![1, 2, 3], vec![1, 2, 4]]);
+    }
+
+    #[test]
+    fn insert_with_and_get() {
+        let mut pt: GenericPrefixTree<char, u32> = GenericPrefixTree::new();
+
+        pt.insert_with("hello", 1);
+        pt.insert_with("help", 2);
+
+        assert_eq!(pt.get("hello"), Some(&1));
+        assert_eq!(pt.get("help"), Some(&2));
+        assert_eq!(pt.get("xyz"), None);
+
+        *pt.get_mut("hello").unwrap() = 100;
+        assert_eq!(pt.get("hello"), Some(&100));
+
+        let mut words = pt.words_with_prefix_values("hel", usize::MAX);
+        words.sort();
+        assert_eq!(
+            words,
+            vec![("hello".to_string(), &100), ("help".to_string(), &2)]
+        );
+    }
+
+    #[test]
+    fn remove_and_len() {
+        let mut pt = PrefixTree::new();
+
+        pt.insert("hello");
+        pt.insert("hell");
+        pt.insert("help");
+        assert_eq!(pt.len(), 3);
+
+        let nodes_before = pt.nodes_total();
+
+        assert_eq!(pt.remove("hello"), true);
+        assert_eq!(pt.len(), 2);
+        assert_eq!(pt.contains_word("hello"), false);
+        assert_eq!(pt.contains_prefix("hel"), true);
+        assert_eq!(pt.contains_word("hell"), true);
+
+        // "hello" added a single "o" node beyond "hell" that removal
+        // should have pruned.
+        assert_eq!(pt.nodes_total(), nodes_before - 1);
+
+        assert_eq!(pt.remove("hello"), false);
+        assert_eq!(pt.len(), 2);
+
+        assert_eq!(pt.remove("hell"), true);
+        assert_eq!(pt.remove("help"), true);
+        assert_eq!(pt.len(), 0);
+        assert_eq!(pt.is_empty(), true);
+    }
+
+    #[test]
+    fn ranked_suggestions() {
+        let mut pt = PrefixTree::new();
+
+        pt.insert("hello");
+        pt.insert("help");
+        pt.insert("world");
+
+        let suggestions = pt.ranked_suggestions("helo", 10);
+        let words: Vec<&str> = suggestions.iter().map(|(word, _)| word.as_str()).collect();
+
+        assert_eq!(words[0], "hello");
+        assert!(!words.contains(&"world"));
+
+        for pair in suggestions.windows(2) {
+            assert!(pair[0].1 >= pair[1].1);
+        }
+
+        assert_eq!(pt.ranked_suggestions("helo", 1).len(), 1);
+        assert_eq!(pt.ranked_suggestions("xyzxyz", 10), Vec::new());
+    }
 }